@@ -22,11 +22,38 @@ use std::{env, fs};
 const LATEST_RAYLIB_VERSION: &str = "3.7.0";
 const LATEST_RAYLIB_API_VERSION: &str = "3";
 
-#[cfg(feature = "nobuild")]
-fn build_with_cmake(_src_path: &str) {}
+// NOTE: `#[cfg(...)]`/`cfg!(...)` reflect the *host* that runs build.rs, not the
+// target being compiled for. Cargo instead exposes the target through
+// CARGO_CFG_TARGET_OS/ARCH/ENV and CARGO_FEATURE_* env vars, which are correct
+// under cross-compilation. All target-conditional decisions in this file go
+// through those env vars (or the `(Platform, PlatformOS)` derived from TARGET)
+// instead of #[cfg]. `debug_assertions` is the one exception worth keeping,
+// and even that is read from PROFILE rather than the attribute.
+fn has_feature(name: &str) -> bool {
+    let env_name = format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"));
+    env::var(env_name).is_ok()
+}
+
+fn is_debug_profile() -> bool {
+    env::var("PROFILE").map(|p| p == "debug").unwrap_or(false)
+}
+
+fn lib_type() -> LibType {
+    if has_feature("shared") {
+        LibType::Shared
+    } else {
+        LibType::Static
+    }
+}
+
+fn build_with_cmake(src_path: &str, settings: &BuildSettings) {
+    if has_feature("nobuild") {
+        return;
+    }
+    let platform = settings.platform;
+    let platform_os = settings.platform_os;
+    let lib_type = settings.lib_type;
 
-#[cfg(not(feature = "nobuild"))]
-fn build_with_cmake(src_path: &str) {
     // CMake uses different lib directories on different systems.
     // I do not know how CMake determines what directory to use,
     // so we will check a few possibilities and use whichever is present.
@@ -41,80 +68,80 @@ fn build_with_cmake(src_path: &str) {
         path
     }
 
-    let target = env::var("TARGET").expect("Cargo build scripts always have TARGET");
-    let (platform, platform_os) = platform_from_target(&target);
-
     let mut conf = cmake::Config::new(src_path);
-    let builder;
-    #[cfg(debug_assertions)]
-    {
-        builder = conf.profile("Debug");
-    }
 
-    #[cfg(not(debug_assertions))]
-    {
-        builder = conf.profile("Release");
+    if is_debug_profile() {
+        conf.profile("Debug");
+    } else {
+        conf.profile("Release");
     }
 
-    builder
-        .define("BUILD_EXAMPLES", "OFF")
+    conf.define("BUILD_EXAMPLES", "OFF")
         .define("CMAKE_BUILD_TYPE", "Release")
         // turn off until this is fixed
         .define("SUPPORT_BUSY_WAIT_LOOP", "OFF");
 
+    if lib_type == LibType::Shared {
+        conf.define("BUILD_SHARED_LIBS", "ON");
+    }
+
     // Enable wayland cmake flag if feature is specified
-    #[cfg(not(target_os = "android"))]
-    {
-        builder.define("USE_WAYLAND", "ON");
-        builder.define("USE_EXTERNAL_GLFW", "ON"); // Necessary for wayland support in my testing
+    // DRM has no GLFW/windowing toolkit to speak of (it drives GBM+DRM+EGL
+    // directly), so it's excluded the same way Android is.
+    if platform != Platform::Android && platform != Platform::Drm {
+        conf.define("USE_WAYLAND", "ON");
+        conf.define("USE_EXTERNAL_GLFW", "ON"); // Necessary for wayland support in my testing
     }
 
     // This seems redundant, but I felt it was needed incase raylib changes it's default
-    #[cfg(not(feature = "wayland"), target_os = "android")]
-    builder.define("USE_WAYLAND", "OFF");
+    if !has_feature("wayland") && platform == Platform::Android {
+        conf.define("USE_WAYLAND", "OFF");
+    }
 
     // Scope implementing flags for forcing OpenGL version
     // See all possible flags at https://github.com/raysan5/raylib/wiki/CMake-Build-Options
     {
-        #[cfg(feature = "opengl_33")]
-        builder.define("OPENGL_VERSION", "3.3");
-
-        #[cfg(feature = "opengl_21")]
-        builder.define("OPENGL_VERSION", "2.1");
-
-        // #[cfg(feature = "opengl_11")]
-        // builder.define("OPENGL_VERSION", "1.1");
-
-        #[cfg(feature = "opengl_es_20")]
-        builder.define("OPENGL_VERSION", "ES 2.0");
-
-        // Once again felt this was necessary incase a default was changed :)
-        #[cfg(not(any(
-            feature = "opengl_33",
-            feature = "opengl_21",
-            // feature = "opengl_11",
-            feature = "opengl_es_20"
-        )))]
-        builder.define("OPENGL_VERSION", "OFF");
+        if has_feature("opengl_33") {
+            conf.define("OPENGL_VERSION", "3.3");
+        } else if has_feature("opengl_21") {
+            conf.define("OPENGL_VERSION", "2.1");
+        // } else if has_feature("opengl_11") {
+        //     conf.define("OPENGL_VERSION", "1.1");
+        } else if has_feature("opengl_es_20") {
+            conf.define("OPENGL_VERSION", "ES 2.0");
+        } else {
+            // Once again felt this was necessary incase a default was changed :)
+            conf.define("OPENGL_VERSION", "OFF");
+        }
     }
     match platform {
         Platform::Desktop => conf.define("PLATFORM", "Desktop"),
         Platform::Web => conf.define("PLATFORM", "Web"),
         Platform::RPI => conf.define("PLATFORM", "Raspberry Pi"),
+        Platform::Drm => conf.define("PLATFORM", "DRM"),
         Platform::Android => {
             // Get the Android NDK path from an environment variable
             let android_ndk_path = env::var("ANDROID_NDK_ROOT")
                 .expect("Please set the ANDROID_NDK_HOME environment variable");
             // Get cmake toolchain file path using ANDROID_HOME environment variable
             let toolchain_file = format!("{android_ndk_path}/build/cmake/android.toolchain.cmake");
-            // Detect ANDROID_ABI using the target triple
-            let android_abi = match target.as_str() {
-                "aarch64-linux-android" => "arm64-v8a",
-                "armv7-linux-androideabi" => "armeabi-v7a",
-                _ => panic!("Unsupported target triple for Android"),
-            };
+            // Prefer Cargo's own notion of the target arch over re-deriving it
+            // from the TARGET triple; fall back to the triple when
+            // CARGO_CFG_TARGET_ARCH is not set.
+            let target = env::var("TARGET").expect("Cargo build scripts always have TARGET");
+            let target_arch = env::var("CARGO_CFG_TARGET_ARCH")
+                .unwrap_or_else(|_| target_arch_from_target(&target).to_owned());
+            let android_abi = android_abi_for_arch(&target_arch);
+            // Let emulator/CI builds target a specific API level (newer NDKs
+            // default to whatever they ship with, which doesn't always match
+            // the emulator image in use) instead of hardcoding one.
+            let android_api_level = env::var("ANDROID_PLATFORM")
+                .or_else(|_| env::var("ANDROID_API_LEVEL"))
+                .unwrap_or_else(|_| "21".to_owned());
+            let android_platform = format_android_platform(&android_api_level);
             conf
                 .define("ANDROID_ABI", android_abi)
+                .define("ANDROID_PLATFORM", &android_platform)
                 .define("CMAKE_TOOLCHAIN_FILE", &toolchain_file)
                 .define("PLATFORM", "Android")
 
@@ -122,27 +149,71 @@ fn build_with_cmake(src_path: &str) {
     };
 
     let dst = conf.build();
-    let dst_lib = join_cmake_lib_directory(dst);
-    // on windows copy the static library to the proper file name
+    // A recent cmake rework upstream renamed the static target to
+    // `raylib_static` and nests build outputs under a `raylib/` subdirectory;
+    // probe both that layout and the old flat one and use whichever one
+    // actually produced a library.
+    let candidate_dirs = [
+        join_cmake_lib_directory(dst.clone()),
+        join_cmake_lib_directory(dst.join("raylib")),
+    ];
+    let dst_lib = candidate_dirs
+        .iter()
+        .find(|dir| lib_dir_has_raylib(dir, lib_type, platform_os))
+        .cloned()
+        .unwrap_or_else(|| candidate_dirs[0].clone());
+
     if platform_os == PlatformOS::Windows {
-        if Path::new(&dst_lib.join("raylib.lib")).exists() {
-            // DO NOTHING
-        } else if Path::new(&dst_lib.join("raylib_static.lib")).exists() {
-            std::fs::copy(
-                dst_lib.join("raylib_static.lib"),
-                dst_lib.join("raylib.lib"),
-            )
-            .expect("failed to create windows library");
-        } else if Path::new(&dst_lib.join("libraylib_static.a")).exists() {
-            std::fs::copy(
-                dst_lib.join("libraylib_static.a"),
-                dst_lib.join("libraylib.a"),
-            )
-            .expect("failed to create windows library");
-        } else if Path::new(&dst_lib.join("libraylib.a")).exists() {
-            // DO NOTHING
-        } else {
-            panic!("failed to create windows library");
+        match lib_type {
+            LibType::Static => {
+                // on windows copy the static library to the proper file name
+                if Path::new(&dst_lib.join("raylib.lib")).exists() {
+                    // DO NOTHING
+                } else if Path::new(&dst_lib.join("raylib_static.lib")).exists() {
+                    std::fs::copy(
+                        dst_lib.join("raylib_static.lib"),
+                        dst_lib.join("raylib.lib"),
+                    )
+                    .expect("failed to create windows library");
+                } else if Path::new(&dst_lib.join("libraylib_static.a")).exists() {
+                    std::fs::copy(
+                        dst_lib.join("libraylib_static.a"),
+                        dst_lib.join("libraylib.a"),
+                    )
+                    .expect("failed to create windows library");
+                } else if Path::new(&dst_lib.join("libraylib.a")).exists() {
+                    // DO NOTHING
+                } else {
+                    panic!("failed to create windows library");
+                }
+            }
+            LibType::Shared => {
+                // MSVC/MinGW both need the import library discoverable as
+                // `raylib`; the .dll itself just needs to exist alongside it
+                // (or be found on PATH) at runtime.
+                if Path::new(&dst_lib.join("raylib.lib")).exists() {
+                    // DO NOTHING
+                } else if Path::new(&dst_lib.join("raylib_shared.lib")).exists() {
+                    std::fs::copy(
+                        dst_lib.join("raylib_shared.lib"),
+                        dst_lib.join("raylib.lib"),
+                    )
+                    .expect("failed to create windows import library");
+                } else if Path::new(&dst_lib.join("libraylib.dll.a")).exists() {
+                    std::fs::copy(
+                        dst_lib.join("libraylib.dll.a"),
+                        dst_lib.join("libraylib.a"),
+                    )
+                    .expect("failed to create windows import library");
+                } else {
+                    panic!("failed to find raylib import library for shared build");
+                }
+                if !Path::new(&dst_lib.join("raylib.dll")).exists()
+                    && !Path::new(&dst_lib.join("raylib_shared.dll")).exists()
+                {
+                    panic!("failed to find raylib.dll for shared build");
+                }
+            }
         }
     } // on web copy libraylib.bc to libraylib.a
     if platform == Platform::Web {
@@ -153,30 +224,110 @@ fn build_with_cmake(src_path: &str) {
     println!("cargo:rustc-link-search=native={}", dst_lib.display());
 }
 
-fn gen_bindings() {
-    let target = env::var("TARGET").expect("Cargo build scripts always have TARGET");
+// android_abi_for_arch maps a Rust target arch (CARGO_CFG_TARGET_ARCH, or the
+// target_arch_from_target fallback below) to the ANDROID_ABI name the NDK's
+// cmake toolchain file expects.
+fn android_abi_for_arch(target_arch: &str) -> &'static str {
+    match target_arch {
+        "aarch64" => "arm64-v8a",
+        "arm" => "armeabi-v7a",
+        "x86_64" => "x86_64",
+        "x86" => "x86",
+        _ => panic!("Unsupported target arch for Android: {target_arch}"),
+    }
+}
+
+// target_arch_from_target infers CARGO_CFG_TARGET_ARCH from a raw TARGET
+// triple, for callers that don't have the env var (e.g. unit tests below).
+// Cargo's arch names don't always match a triple's first component 1:1
+// (armv7-* targets report CARGO_CFG_TARGET_ARCH = "arm"), so this only needs
+// to cover the triples platform_from_target resolves to Platform::Android.
+fn target_arch_from_target(target: &str) -> &'static str {
+    if target.starts_with("aarch64") {
+        "aarch64"
+    } else if target.starts_with("armv7") {
+        "arm"
+    } else if target.starts_with("x86_64") {
+        "x86_64"
+    } else if target.starts_with("i686") {
+        "x86"
+    } else {
+        panic!("Unsupported target arch for Android: {target}")
+    }
+}
+
+// format_android_platform normalizes an ANDROID_PLATFORM/ANDROID_API_LEVEL
+// value (e.g. "21") into the "android-<level>" form cmake's Android toolchain
+// file expects, passing already-prefixed values through unchanged.
+fn format_android_platform(level: &str) -> String {
+    if level.starts_with("android-") {
+        level.to_owned()
+    } else {
+        format!("android-{level}")
+    }
+}
+
+// lib_dir_has_raylib checks whether `dir` contains a raylib build output for
+// the given (LibType, PlatformOS), across the old flat cmake layout and the
+// newer one that nests outputs under a `raylib/` subdirectory.
+fn lib_dir_has_raylib(dir: &Path, lib_type: LibType, platform_os: PlatformOS) -> bool {
+    if !dir.exists() {
+        return false;
+    }
+    match (lib_type, platform_os) {
+        (LibType::Static, PlatformOS::Windows) => {
+            dir.join("raylib.lib").exists()
+                || dir.join("raylib_static.lib").exists()
+                || dir.join("libraylib_static.a").exists()
+                || dir.join("libraylib.a").exists()
+        }
+        (LibType::Shared, PlatformOS::Windows) => {
+            dir.join("raylib.dll").exists() || dir.join("raylib_shared.dll").exists()
+        }
+        (LibType::Static, PlatformOS::OSX) => dir.join("libraylib.a").exists(),
+        (LibType::Shared, PlatformOS::OSX) => dir.join("libraylib.dylib").exists(),
+        (LibType::Static, _) => dir.join("libraylib.a").exists(),
+        (LibType::Shared, _) => dir.join("libraylib.so").exists(),
+    }
+}
+
+fn gen_bindings(src_path: &str, platform: Platform, platform_os: PlatformOS) {
     let out_dir =
         PathBuf::from(env::var("OUT_DIR").expect("Cargo build scripts always have an OUT_DIR"));
 
-    let (platform, platform_os) = platform_from_target(&target);
+    // The `bindgen` feature always generates live from the C headers, even on
+    // platforms that do have a pre-baked bindings file, for anyone who wants
+    // bindings matching their exact local raylib checkout. musl libc targets
+    // get the same treatment unconditionally: the pre-baked Linux bindings
+    // were generated against glibc headers, so a musl target falling into the
+    // `PlatformOS::Linux` arm below would get bindings that don't match its
+    // actual libc.
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    if has_feature("bindgen") || target_env == "musl" {
+        gen_bindings_with_bindgen(src_path, platform, &out_dir);
+        return;
+    }
 
-    // Generate bindings
+    // Pre-baked bindings only exist for Desktop builds on the three OSes we
+    // ship them for, plus Web. Match on `Platform` first for those cases so
+    // Android/RPI/Drm never fall into `PlatformOS::Linux`'s pre-baked file
+    // just because platform_from_target also maps them to PlatformOS::Linux.
     match (platform, platform_os) {
-        (_, PlatformOS::Windows) => {
+        (Platform::Desktop, PlatformOS::Windows) => {
             fs::write(
                 out_dir.join("bindings.rs"),
                 include_str!("bindings_windows.rs"),
             )
             .expect("failed to write bindings");
         }
-        (_, PlatformOS::Linux) => {
+        (Platform::Desktop, PlatformOS::Linux) => {
             fs::write(
                 out_dir.join("bindings.rs"),
                 include_str!("bindings_linux.rs"),
             )
             .expect("failed to write bindings");
         }
-        (_, PlatformOS::OSX) => {
+        (Platform::Desktop, PlatformOS::OSX) => {
             fs::write(out_dir.join("bindings.rs"), include_str!("bindings_osx.rs"))
                 .expect("failed to write bindings");
         }
@@ -184,15 +335,75 @@ fn gen_bindings() {
             fs::write(out_dir.join("bindings.rs"), include_str!("bindings_web.rs"))
                 .expect("failed to write bindings");
         }
-        // for other platforms use bindgen and hope it works
-        _ => panic!("raylib-rs not supported on your platform"),
+        // BSD, Android, RPI, Drm, and anything else without a pre-baked
+        // bindings file: generate the FFI straight from the C headers instead
+        // of panicking (or, as before this fix, silently writing bindings
+        // baked for a different platform).
+        _ => gen_bindings_with_bindgen(src_path, platform, &out_dir),
+    }
+}
+
+// gen_bindings_with_bindgen runs bindgen against the raylib headers under
+// `src_path` (the vendored copy, a downloaded release or RAYLIB_SRC_PATH) using the same -DPLATFORM_*/-DGRAPHICS_API_OPENGL_* defines the cmake build
+// picked for `platform`, and the target bindgen was asked to generate for
+// (bindgen defaults to the host otherwise, which breaks cross-compilation the
+// same way host #[cfg] attributes did).
+fn gen_bindings_with_bindgen(src_path: &str, platform: Platform, out_dir: &Path) {
+    let target = env::var("TARGET").expect("Cargo build scripts always have TARGET");
+    let src_dir = Path::new(src_path).join("src");
+
+    let bindings = bindgen::Builder::default()
+        .header(src_dir.join("raylib.h").to_string_lossy())
+        .header(src_dir.join("rlgl.h").to_string_lossy())
+        .header(src_dir.join("raymath.h").to_string_lossy())
+        .clang_arg(format!("-I{}", src_dir.display()))
+        .clang_arg(format!("-D{}", raylib_platform_define(platform)))
+        .clang_arg(format!("-D{}", raylib_graphics_api_define(platform)))
+        .clang_arg(format!("--target={target}"))
+        .generate()
+        .expect("failed to generate bindgen bindings for raylib");
+
+    bindings
+        .write_to_file(out_dir.join("bindings.rs"))
+        .expect("failed to write bindgen bindings");
+}
+
+// raylib_platform_define mirrors the `PLATFORM` cmake define selected in
+// build_with_cmake, as the matching config.h preprocessor define.
+fn raylib_platform_define(platform: Platform) -> &'static str {
+    match platform {
+        Platform::Desktop => "PLATFORM_DESKTOP",
+        Platform::Web => "PLATFORM_WEB",
+        Platform::Android => "PLATFORM_ANDROID",
+        Platform::RPI => "PLATFORM_RPI",
+        Platform::Drm => "PLATFORM_DRM",
     }
 }
 
-fn gen_rgui() {
+// raylib_graphics_api_define mirrors the `OPENGL_VERSION` cmake define
+// selected in build_with_cmake, as the matching config.h preprocessor define,
+// falling back to each platform's raylib default when no opengl_* feature is
+// enabled.
+fn raylib_graphics_api_define(platform: Platform) -> &'static str {
+    if has_feature("opengl_33") {
+        "GRAPHICS_API_OPENGL_33"
+    } else if has_feature("opengl_21") {
+        "GRAPHICS_API_OPENGL_21"
+    } else if has_feature("opengl_es_20") {
+        "GRAPHICS_API_OPENGL_ES2"
+    } else {
+        match platform {
+            Platform::Desktop => "GRAPHICS_API_OPENGL_33",
+            Platform::Web | Platform::Android | Platform::RPI | Platform::Drm => {
+                "GRAPHICS_API_OPENGL_ES2"
+            }
+        }
+    }
+}
+
+fn gen_rgui(platform_os: PlatformOS) {
     // Compile the code and link with cc crate
-    #[cfg(target_os = "windows")]
-    {
+    if platform_os == PlatformOS::Windows {
         cc::Build::new()
             .file("rgui_wrapper.cpp")
             .include(".")
@@ -200,9 +411,7 @@ fn gen_rgui() {
             // .flag("-std=c99")
             .extra_warnings(false)
             .compile("rgui");
-    }
-    #[cfg(not(target_os = "windows"))]
-    {
+    } else {
         cc::Build::new()
             .file("rgui_wrapper.c")
             .include(".")
@@ -213,11 +422,13 @@ fn gen_rgui() {
     }
 }
 
-#[cfg(feature = "nobuild")]
-fn link(_platform: Platform, _platform_os: PlatformOS) {}
+fn link(settings: &BuildSettings) {
+    if has_feature("nobuild") {
+        return;
+    }
+    let platform = settings.platform;
+    let platform_os = settings.platform_os;
 
-#[cfg(not(feature = "nobuild"))]
-fn link(platform: Platform, platform_os: PlatformOS) {
     match platform_os {
         PlatformOS::Windows => {
             println!("cargo:rustc-link-lib=dylib=winmm");
@@ -226,19 +437,18 @@ fn link(platform: Platform, platform_os: PlatformOS) {
             println!("cargo:rustc-link-lib=dylib=shell32");
         }
         PlatformOS::Linux => {
-            // X11 linking
-            #[cfg(not(feature = "wayland"))]
-            {
-                println!("cargo:rustc-link-search=/usr/local/lib");
-                println!("cargo:rustc-link-lib=X11");
-            }
-
-            // Wayland linking
-            #[cfg(feature = "wayland")]
-            {
+            if platform == Platform::Drm {
+                // DRM drives the display through GBM+EGL; no X11 or Wayland
+                // libraries are linked (see the Drm libraries below).
+            } else if has_feature("wayland") {
+                // Wayland linking
                 println!("cargo:rustc-link-search=/usr/local/lib");
                 println!("cargo:rustc-link-lib=wayland-client");
                 println!("cargo:rustc-link-lib=glfw"); // Link against locally installed glfw
+            } else {
+                // X11 linking
+                println!("cargo:rustc-link-search=/usr/local/lib");
+                println!("cargo:rustc-link-lib=X11");
             }
         }
         PlatformOS::OSX => {
@@ -259,24 +469,41 @@ fn link(platform: Platform, platform_os: PlatformOS) {
         println!("cargo:rustc-link-lib=brcmEGL");
         println!("cargo:rustc-link-lib=brcmGLESv2");
         println!("cargo:rustc-link-lib=vcos");
+    } else if platform == Platform::Drm {
+        // Pi 4 / headless KMS: V3D+fkms over GBM+DRM+EGL (EGL_NO_X11), no
+        // Broadcom /opt/vc stack and no X server required.
+        println!("cargo:rustc-link-lib=GLESv2");
+        println!("cargo:rustc-link-lib=EGL");
+        println!("cargo:rustc-link-lib=drm");
+        println!("cargo:rustc-link-lib=gbm");
+        println!("cargo:rustc-link-lib=rt");
     }
 
-    println!("cargo:rustc-link-lib=static=raylib");
+    match settings.lib_type {
+        LibType::Static => println!("cargo:rustc-link-lib=static=raylib"),
+        LibType::Shared => println!("cargo:rustc-link-lib=dylib=raylib"),
+    }
 }
 
 fn main() {
     let target = env::var("TARGET").expect("Cargo build scripts always have TARGET");
     let (platform, platform_os) = platform_from_target(&target);
+    let settings = BuildSettings {
+        platform,
+        platform_os,
+        lib_type: lib_type(),
+        bundled_glfw: platform == Platform::Android || platform == Platform::Drm,
+    };
 
     // Donwload raylib source
     let src = cp_raylib();
-    build_with_cmake(&src);
+    build_with_cmake(&src, &settings);
 
-    gen_bindings();
+    gen_bindings(&src, platform, platform_os);
 
-    link(platform, platform_os);
+    link(&settings);
 
-    gen_rgui();
+    gen_rgui(platform_os);
 }
 
 // cp_raylib copy raylib to an out dir
@@ -284,6 +511,20 @@ fn cp_raylib() -> String {
     let out = env::var("OUT_DIR").unwrap();
     let out = Path::new(&out); //.join("raylib_source");
 
+    // Point at an out-of-tree checkout (e.g. a git submodule set up via
+    // `git submodule update --init`) instead of the vendored copy.
+    if let Ok(src_path) = env::var("RAYLIB_SRC_PATH") {
+        return src_path;
+    }
+
+    // Only the vendored copy is pinned to LATEST_RAYLIB_VERSION; anything
+    // else is fetched from the matching GitHub release so users can target a
+    // specific raylib API version without recompiling the crate to change it.
+    let version = env::var("RAYLIB_VERSION").unwrap_or_else(|_| LATEST_RAYLIB_VERSION.to_owned());
+    if version != LATEST_RAYLIB_VERSION {
+        return download_raylib(&version, out);
+    }
+
     let mut options = fs_extra::dir::CopyOptions::new();
     options.skip_exist = true;
     fs_extra::dir::copy("raylib", &out, &options).expect(&format!(
@@ -294,6 +535,47 @@ fn cp_raylib() -> String {
     out.join("raylib").to_string_lossy().to_string()
 }
 
+// download_raylib fetches the raylib `version` source tarball from the
+// raysan5/raylib GitHub releases into `out`, extracts it, sanity-checks that
+// the result actually looks like a raylib source tree, and returns its path.
+fn download_raylib(version: &str, out: &Path) -> String {
+    let extracted_dir = out.join(format!("raylib-{}", version.trim_start_matches('v')));
+
+    // Mirror the vendored-copy fast path above: if this version was already
+    // fetched and extracted by a previous build-script run, reuse it instead
+    // of re-hitting GitHub on every rerun triggered by some unrelated
+    // invalidated env var.
+    if extracted_dir.join("src").join("raylib.h").exists() {
+        return extracted_dir.to_string_lossy().to_string();
+    }
+
+    let archive_path = out
+        .join(format!("raylib-{version}.tar.gz"))
+        .to_string_lossy()
+        .to_string();
+    let url = format!("https://github.com/raysan5/raylib/archive/refs/tags/{version}.tar.gz");
+
+    run_command("curl", &["-L", "-f", "-o", &archive_path, &url]);
+
+    if extracted_dir.exists() {
+        fs::remove_dir_all(&extracted_dir).expect("failed to clean up stale raylib checkout");
+    }
+    run_command(
+        "tar",
+        &["-xzf", &archive_path, "-C", &out.to_string_lossy()],
+    );
+
+    if !extracted_dir.join("src").join("raylib.h").exists() {
+        panic!(
+            "downloaded raylib {} but {} doesn't look like a raylib source tree",
+            version,
+            extracted_dir.display()
+        );
+    }
+
+    extracted_dir.to_string_lossy().to_string()
+}
+
 // run_command runs a command to completion or panics. Used for running curl and powershell.
 fn run_command(cmd: &str, args: &[&str]) {
     use std::process::Command;
@@ -310,45 +592,58 @@ fn run_command(cmd: &str, args: &[&str]) {
     }
 }
 
+// platform_from_target derives (Platform, PlatformOS) purely from the TARGET
+// triple (plus the `drm` feature and, when set, CARGO_CFG_TARGET_OS). It must
+// stay host-independent: this is the single source of truth threaded through
+// build_with_cmake/gen_bindings/link/gen_rgui so none of them need to fall
+// back to #[cfg(target_os = "...")] or uname() themselves.
 fn platform_from_target(target: &str) -> (Platform, PlatformOS) {
-    let platform = if target.contains("wasm32") {
+    let platform = if has_feature("drm") {
+        // Explicit opt-in always wins, e.g. cross-compiling the legacy
+        // armv7 RPI triple for a Pi 4 running headless KMS instead of the
+        // Broadcom /opt/vc stack.
+        Platform::Drm
+    } else if target.contains("wasm32") {
         // make sure cmake knows that it should bundle glfw in
         // Cargo web takes care of this but better safe than sorry
         env::set_var("EMMAKEN_CFLAGS", "-s USE_GLFW=3");
         Platform::Web
     } else if target.contains("armv7-unknown-linux") {
         Platform::RPI
-    } else if target.ends_with("linux-android") {
+    } else if target.contains("-android") {
+        // Covers aarch64-linux-android, x86_64-linux-android,
+        // i686-linux-android (end with "linux-android"), and
+        // armv7-linux-androideabi (ends with "androideabi", not
+        // "linux-android" — an `ends_with` check missed this physical-device
+        // ABI entirely).
         Platform::Android
     } else {
         Platform::Desktop
     };
 
     let platform_os = if platform == Platform::Desktop {
-        // Determine PLATFORM_OS in case PLATFORM_DESKTOP selected
-        if env::var("OS")
-            .unwrap_or("".to_owned())
-            .contains("Windows_NT")
-            || env::var("TARGET")
-                .unwrap_or("".to_owned())
-                .contains("windows")
-        {
-            // No uname.exe on MinGW!, but OS=Windows_NT on Windows!
-            // ifeq ($(UNAME),Msys) -> Windows
+        // Determine PlatformOS from the target triple/CARGO_CFG_TARGET_OS
+        // rather than the host's `uname`/`OS`, so cross-compiling a desktop
+        // target (e.g. building for Windows from a Linux host) picks the
+        // right OS instead of the one running the build script.
+        let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+        if target_os == "windows" || target.contains("windows") {
             PlatformOS::Windows
+        } else if target_os == "linux" || target.contains("linux") {
+            PlatformOS::Linux
+        } else if target_os == "macos" || target.contains("apple-darwin") {
+            PlatformOS::OSX
+        } else if matches!(target_os.as_str(), "freebsd" | "openbsd" | "netbsd" | "dragonfly")
+            || target.contains("freebsd")
+            || target.contains("openbsd")
+            || target.contains("netbsd")
+            || target.contains("dragonfly")
+        {
+            PlatformOS::BSD
         } else {
-            let un: &str = &uname();
-            match un {
-                "Linux" => PlatformOS::Linux,
-                "FreeBSD" => PlatformOS::BSD,
-                "OpenBSD" => PlatformOS::BSD,
-                "NetBSD" => PlatformOS::BSD,
-                "DragonFly" => PlatformOS::BSD,
-                "Darwin" => PlatformOS::OSX,
-                _ => panic!("Unknown platform {}", uname()),
-            }
+            panic!("Unknown platform for target {}", target)
         }
-    } else if matches!(platform, Platform::RPI | Platform::Android) {
+    } else if matches!(platform, Platform::RPI | Platform::Android | Platform::Drm) {
         PlatformOS::Linux
     } else {
         PlatformOS::Unknown
@@ -357,24 +652,13 @@ fn platform_from_target(target: &str) -> (Platform, PlatformOS) {
     (platform, platform_os)
 }
 
-fn uname() -> String {
-    use std::process::Command;
-    String::from_utf8_lossy(
-        &Command::new("uname")
-            .output()
-            .expect("failed to run uname")
-            .stdout,
-    )
-    .trim()
-    .to_owned()
-}
-
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Platform {
     Web,
     Desktop,
     Android,
-    RPI, // raspberry pi
+    RPI, // raspberry pi (legacy Broadcom /opt/vc stack)
+    Drm, // raspberry pi 4 / headless KMS (GBM+DRM+EGL, no X11)
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -386,10 +670,10 @@ enum PlatformOS {
     Unknown,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum LibType {
     Static,
-    _Shared,
+    Shared,
 }
 
 #[derive(Debug, PartialEq)]
@@ -401,5 +685,111 @@ enum BuildMode {
 struct BuildSettings {
     pub platform: Platform,
     pub platform_os: PlatformOS,
+    pub lib_type: LibType,
     pub bundled_glfw: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross_compiling_to_android_resolves_android_linux_regardless_of_host() {
+        // A cross build for aarch64 Android must resolve to Android/Linux
+        // regardless of what host (e.g. this very test runner) is executing.
+        let (platform, platform_os) = platform_from_target("aarch64-linux-android");
+        assert_eq!(platform, Platform::Android);
+        assert_eq!(platform_os, PlatformOS::Linux);
+    }
+
+    #[test]
+    fn cross_compiling_to_windows_resolves_windows_os_from_target_not_host() {
+        // Cross-compiling to Windows from a non-Windows host must still pick
+        // PlatformOS::Windows, since it's derived from TARGET, not the host.
+        let (platform, platform_os) = platform_from_target("x86_64-pc-windows-msvc");
+        assert_eq!(platform, Platform::Desktop);
+        assert_eq!(platform_os, PlatformOS::Windows);
+    }
+
+    #[test]
+    fn armv7_hardfloat_linux_defaults_to_legacy_rpi_platform() {
+        let (platform, platform_os) = platform_from_target("armv7-unknown-linux-gnueabihf");
+        assert_eq!(platform, Platform::RPI);
+        assert_eq!(platform_os, PlatformOS::Linux);
+    }
+
+    #[test]
+    fn wasm32_resolves_to_web_platform() {
+        let (platform, platform_os) = platform_from_target("wasm32-unknown-unknown");
+        assert_eq!(platform, Platform::Web);
+        assert_eq!(platform_os, PlatformOS::Unknown);
+    }
+
+    #[test]
+    fn aarch64_gnu_linux_defaults_to_desktop_not_drm() {
+        // aarch64-unknown-linux-gnu is the standard triple for arm64 desktop
+        // distros, cloud servers, and Asahi Linux, not just Pi 4 headless
+        // boards, so it must not be hijacked into DRM by default; only the
+        // explicit `drm` feature may select Platform::Drm.
+        let (platform, platform_os) = platform_from_target("aarch64-unknown-linux-gnu");
+        assert_eq!(platform, Platform::Desktop);
+        assert_eq!(platform_os, PlatformOS::Linux);
+    }
+
+    #[test]
+    fn android_emulator_abis_are_recognized_like_physical_device_abis() {
+        // x86_64/i686 Android emulator targets should be recognized the same
+        // way physical-device ABIs are.
+        let (platform, platform_os) = platform_from_target("x86_64-linux-android");
+        assert_eq!(platform, Platform::Android);
+        assert_eq!(platform_os, PlatformOS::Linux);
+
+        let (platform, platform_os) = platform_from_target("i686-linux-android");
+        assert_eq!(platform, Platform::Android);
+        assert_eq!(platform_os, PlatformOS::Linux);
+    }
+
+    #[test]
+    fn armv7_android_physical_device_abi_resolves_to_android_not_rpi() {
+        // armv7-linux-androideabi is the flagship physical-device ABI this
+        // match arm has handled since baseline, but doesn't end with the
+        // literal substring "linux-android" like the other three triples do.
+        let (platform, platform_os) = platform_from_target("armv7-linux-androideabi");
+        assert_eq!(platform, Platform::Android);
+        assert_eq!(platform_os, PlatformOS::Linux);
+    }
+
+    #[test]
+    fn android_abi_define_for_aarch64_target_is_arm64_v8a() {
+        let target_arch = target_arch_from_target("aarch64-linux-android");
+        assert_eq!(android_abi_for_arch(target_arch), "arm64-v8a");
+    }
+
+    #[test]
+    fn android_abi_define_for_armv7_target_is_armeabi_v7a() {
+        let target_arch = target_arch_from_target("armv7-linux-androideabi");
+        assert_eq!(android_abi_for_arch(target_arch), "armeabi-v7a");
+    }
+
+    #[test]
+    fn android_abi_define_for_x86_64_target_is_x86_64() {
+        let target_arch = target_arch_from_target("x86_64-linux-android");
+        assert_eq!(android_abi_for_arch(target_arch), "x86_64");
+    }
+
+    #[test]
+    fn android_abi_define_for_i686_target_is_x86() {
+        let target_arch = target_arch_from_target("i686-linux-android");
+        assert_eq!(android_abi_for_arch(target_arch), "x86");
+    }
+
+    #[test]
+    fn android_platform_define_prefixes_a_bare_api_level() {
+        assert_eq!(format_android_platform("21"), "android-21");
+    }
+
+    #[test]
+    fn android_platform_define_passes_through_an_already_prefixed_value() {
+        assert_eq!(format_android_platform("android-30"), "android-30");
+    }
+}